@@ -0,0 +1,159 @@
+// Advances one lane's Bresenham-style schedule by a single step and returns how many source
+// elements it should consume this step.
+fn step_advance(schedule: &mut (usize, usize, usize), len_min: usize) -> usize {
+    let (base, rem, error) = schedule;
+    let mut advance = *base;
+    *error += *rem;
+    if *error >= len_min {
+        *error -= len_min;
+        advance += 1;
+    }
+    advance
+}
+
+/// An iterator which iterates multiple other iterators simultaneously, always returning
+/// elements evenly sampled from the longer ones so that all of them are squashed down to the
+/// length of the shortest.
+///
+/// See [`izip_squash!`](crate::izip_squash) for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct MultiZipSquash<T> {
+    lanes: T,
+    // Per-lane `(base, rem, error)`, in the same order as the lanes in `T`. See
+    // [`ZipSquash`](crate::ZipSquash) for what these mean; here every lane is squashed using
+    // the same schedule that `ZipSquash` uses for its longer side.
+    schedules: Vec<(usize, usize, usize)>,
+    len_min: usize,
+    remaining: usize,
+}
+
+/// Zips multiple iterators together, squashing every iterator down to the length of the
+/// shortest one.
+///
+/// [`IntoIterator`] enabled version of [`izip_squash!`](crate::izip_squash). Most users should
+/// prefer the macro, which also accepts iterators directly without needing them bundled into a
+/// tuple.
+pub fn multizip_squash<T, U>(t: U) -> MultiZipSquash<T>
+where
+    MultiZipSquash<T>: From<U>,
+{
+    MultiZipSquash::from(t)
+}
+
+macro_rules! impl_multi_zip_squash {
+    ($($T:ident $t:ident),+) => {
+        impl<$($T),+> From<($($T,)+)> for MultiZipSquash<($($T::IntoIter,)+)>
+        where
+            $($T: IntoIterator,)+
+            $($T::IntoIter: ExactSizeIterator,)+
+        {
+            fn from(t: ($($T,)+)) -> Self {
+                let ($($t,)+) = t;
+                $(let $t = $t.into_iter();)+
+                let len_min = [$($t.len()),+].into_iter().min().unwrap();
+                let schedules = vec![$({
+                    let len = $t.len();
+                    let (base, rem) = if len_min == 0 {
+                        (0, 0)
+                    } else {
+                        (len / len_min, len % len_min)
+                    };
+                    // Start halfway through the first window so sampled points are centered
+                    // rather than biased towards the start of the lane.
+                    (base, rem, len_min / 2)
+                }),+];
+                MultiZipSquash {
+                    lanes: ($($t,)+),
+                    schedules,
+                    len_min,
+                    remaining: len_min,
+                }
+            }
+        }
+
+        impl<$($T),+> Iterator for MultiZipSquash<($($T,)+)>
+        where
+            $($T: ExactSizeIterator,)+
+        {
+            type Item = ($($T::Item,)+);
+
+            fn next(&mut self) -> Option<Self::Item> {
+                if self.remaining == 0 {
+                    return None;
+                }
+                self.remaining -= 1;
+                let len_min = self.len_min;
+                let mut schedules = self.schedules.iter_mut();
+                let ($($t,)+) = &mut self.lanes;
+                Some(($({
+                    let advance = step_advance(schedules.next().unwrap(), len_min);
+                    $t.nth(advance - 1)?
+                },)+))
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (self.remaining, Some(self.remaining))
+            }
+        }
+
+        impl<$($T),+> ExactSizeIterator for MultiZipSquash<($($T,)+)>
+        where
+            $($T: ExactSizeIterator,)+
+        {
+        }
+    };
+}
+
+impl_multi_zip_squash!(A a, B b);
+impl_multi_zip_squash!(A a, B b, C c);
+impl_multi_zip_squash!(A a, B b, C c, D d);
+impl_multi_zip_squash!(A a, B b, C c, D d, E e);
+impl_multi_zip_squash!(A a, B b, C c, D d, E e, F f);
+impl_multi_zip_squash!(A a, B b, C c, D d, E e, F f, G g);
+impl_multi_zip_squash!(A a, B b, C c, D d, E e, F f, G g, H h);
+
+/// Creates an iterator that squashes every argument iterator down to the length of the
+/// shortest, evenly sampling the longer ones, and yields the results in a tuple.
+///
+/// This is the squashing counterpart of [`izip!`](crate::izip), usable with 2 to 8
+/// [`ExactSizeIterator`]s; more than 8 arguments do not compile.
+///
+/// ```
+/// use itertools::izip_squash;
+///
+/// let xs = [0, 1, 2, 3, 4, 5];
+/// let ys = [10, 20, 30];
+/// let zs = [100, 200, 300, 400, 500, 600];
+/// let squashed: Vec<_> = izip_squash!(xs, ys, zs).collect();
+/// assert_eq!(squashed.len(), 3);
+/// ```
+#[macro_export]
+macro_rules! izip_squash {
+    ($($i:expr),+ $(,)?) => {
+        $crate::multizip_squash(($($i,)+))
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn arity_three_samples_the_exact_expected_tuples() {
+        let out: Vec<_> = crate::izip_squash!(0..6, 0..3, 0..6).collect();
+        assert_eq!(out, vec![(1, 0, 1), (3, 1, 3), (5, 2, 5)]);
+    }
+
+    #[test]
+    fn one_empty_lane_makes_the_whole_thing_empty() {
+        let mut empty = crate::izip_squash!(0..6, 0..0, 0..6);
+        assert_eq!(empty.next(), None);
+        assert_eq!(empty.len(), 0);
+    }
+
+    #[test]
+    fn equal_lengths_is_a_plain_zip() {
+        let out: Vec<_> = crate::izip_squash!(0..5, 0..5, 0..5).collect();
+        let expected: Vec<_> = (0..5).map(|i| (i, i, i)).collect();
+        assert_eq!(out, expected);
+    }
+}
@@ -1,5 +1,4 @@
 use super::size_hint;
-use std::cmp::Ordering;
 
 /// An iterator which iterates two other iterators simultaneously
 /// always returning elements are evenly sampled from the longest iterator.
@@ -10,16 +9,41 @@ use std::cmp::Ordering;
 pub struct ZipSquash<I: ExactSizeIterator, J: ExactSizeIterator> {
     a: I,
     b: J,
-    a_delta: f32,
-    b_delta: f32,
-    a_index: f32,
-    b_index: f32,
+    // Whether `a` is the iterator being squashed down to `len_short` elements.
+    a_is_long: bool,
+    // Length of the longer of the two iterators at construction time.
+    len_long: usize,
+    // Length of the shorter of the two iterators at construction time, i.e. the number of
+    // pairs this iterator will yield.
+    len_short: usize,
+    // `len_long / len_short`: the minimum number of long-side elements consumed per pair.
+    base: usize,
+    // `len_long % len_short`: how often an extra long-side element must be consumed to make
+    // up the difference.
+    rem: usize,
+    // Initial value of the Bresenham-style error accumulator, in `[0, len_short)`.
+    start_error: usize,
+    // Number of pairs already yielded from the front.
+    steps_done: usize,
+    // Number of pairs already yielded from the back.
+    back_steps: usize,
+    // Number of long-side elements already consumed from the back.
+    back_long_consumed: usize,
 }
 
 /// Zips two iterators skipping elements of the longest iterator to ensure it fully consumes both
 /// iterators.
 ///
 /// [`IntoIterator`] enabled version of [`Itertools::zip_squash`](crate::Itertools::zip_squash).
+///
+/// ```
+/// use itertools::zip_squash;
+///
+/// let long = 0..10;
+/// let short = 0..3;
+/// let squashed: Vec<_> = zip_squash(long, short).collect();
+/// assert_eq!(squashed, vec![(2, 0), (6, 1), (9, 2)]);
+/// ```
 pub fn zip_squash<I, J>(i: I, j: J) -> ZipSquash<I::IntoIter, J::IntoIter>
 where
     I: IntoIterator,
@@ -27,22 +51,40 @@ where
     <I as IntoIterator>::IntoIter: ExactSizeIterator,
     <J as IntoIterator>::IntoIter: ExactSizeIterator,
 {
-    use std::iter::ExactSizeIterator;
     let (a, b) = (i.into_iter(), j.into_iter());
-    let (a_delta, b_delta) = match a.len().cmp(&b.len()) {
-        Ordering::Equal => (1f32, 1f32),
-        Ordering::Less => (1f32, b.len() as f32 / a.len() as f32),
-        Ordering::Greater => (a.len() as f32 / b.len() as f32, 1f32),
-    };
-    debug_assert!(a_delta >= 1f32);
-    debug_assert!(b_delta >= 1f32);
+    let (len_a, len_b) = (a.len(), b.len());
+    let len_long = len_a.max(len_b);
+    let len_short = len_a.min(len_b);
+    let base = len_long.checked_div(len_short).unwrap_or(0);
+    let rem = len_long.checked_rem(len_short).unwrap_or(0);
     ZipSquash {
         a,
         b,
-        a_delta,
-        b_delta,
-        a_index: 0f32,
-        b_index: 0f32,
+        a_is_long: len_a >= len_b,
+        len_long,
+        len_short,
+        base,
+        rem,
+        // Start halfway through the first window so sampled points are centered rather than
+        // biased towards the start of the long iterator.
+        start_error: len_short / 2,
+        steps_done: 0,
+        back_steps: 0,
+        back_long_consumed: 0,
+    }
+}
+
+impl<I, J> ZipSquash<I, J>
+where
+    I: ExactSizeIterator,
+    J: ExactSizeIterator,
+{
+    // Total number of long-side elements consumed by the first `steps` pairs. This is a
+    // closed form of the Bresenham-style schedule: it depends only on `steps`, not on any
+    // mutable state, so it can be evaluated for an arbitrary `steps` in `O(1)` to support
+    // skipping ahead in `nth`.
+    fn cum_long(&self, steps: usize) -> usize {
+        steps * self.base + (self.start_error + steps * self.rem) / self.len_short
     }
 }
 
@@ -54,23 +96,39 @@ where
     type Item = (I::Item, J::Item);
 
     fn next(&mut self) -> Option<Self::Item> {
-        let (a, b) = (self.a.next(), self.b.next());
-        let a_diff = (self.a_delta / (1f32 - self.a_index.fract())).ceil() as usize;
-        self.a_index += a_diff as f32 * self.a_delta;
-        if let Some(skip) = a_diff.checked_sub(2) {
-            self.a.nth(skip);
-        }
+        self.nth(0)
+    }
 
-        let b_diff = (self.b_delta / (1f32 - self.b_index.fract())).ceil() as usize;
-        self.b_index += b_diff as f32 * self.b_delta;
-        if let Some(skip) = b_diff.checked_sub(2) {
-            self.b.nth(skip);
+    /// Skips ahead by computing the sampled indices for the `n`-th upcoming pair in closed
+    /// form, rather than by calling `next()` `n + 1` times, so skipping ahead in a
+    /// `zip_squash` over large slices is `O(1)` rather than `O(n)`.
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let steps_before = self.steps_done;
+        let target_steps = steps_before + n + 1;
+        if target_steps > self.len_short - self.back_steps {
+            self.steps_done = self.len_short - self.back_steps;
+            return None;
         }
+        // Number of long-side elements to skip to land exactly on the element sampled for
+        // the `target_steps`-th pair, computed in closed form rather than by stepping
+        // through the intervening pairs one at a time.
+        let long_skip = self.cum_long(target_steps) - self.cum_long(steps_before) - 1;
+        self.steps_done = target_steps;
 
-        match (a, b) {
-            (None, None) => None,
-            (Some(a), Some(b)) => Some((a, b)),
-            (None, Some(_)) | (Some(_), None) => unreachable!(),
+        if self.a_is_long {
+            let a = self.a.nth(long_skip);
+            let b = self.b.nth(n);
+            match (a, b) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            }
+        } else {
+            let a = self.a.nth(n);
+            let b = self.b.nth(long_skip);
+            match (a, b) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            }
         }
     }
 
@@ -85,3 +143,133 @@ where
     J: ExactSizeIterator,
 {
 }
+
+impl<I, J> DoubleEndedIterator for ZipSquash<I, J>
+where
+    I: ExactSizeIterator + DoubleEndedIterator,
+    J: ExactSizeIterator + DoubleEndedIterator,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.steps_done + self.back_steps >= self.len_short {
+            return None;
+        }
+        self.back_steps += 1;
+        // The 1-indexed pair being yielded, counting from the front, i.e. the highest pair
+        // not yet yielded from either end.
+        let target_step = self.len_short - self.back_steps + 1;
+
+        // Total long-side elements that must have been removed from the back, including
+        // this pair, for the next element popped off the back to be the one sampled for
+        // `target_step`.
+        let new_back_long = self.len_long - self.cum_long(target_step) + 1;
+        let skip = new_back_long - self.back_long_consumed - 1;
+        self.back_long_consumed = new_back_long;
+
+        if self.a_is_long {
+            let a = self.a.nth_back(skip);
+            let b = self.b.next_back();
+            match (a, b) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            }
+        } else {
+            let a = self.a.next_back();
+            let b = self.b.nth_back(skip);
+            match (a, b) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zip_squash;
+
+    #[test]
+    fn length_is_the_shorter_iterator() {
+        let out: Vec<_> = zip_squash(0..97, 0..13).collect();
+        assert_eq!(out.len(), 13);
+    }
+
+    #[test]
+    fn long_side_is_fully_consumed() {
+        // Every element the long iterator ever produces must be accounted for by some window,
+        // which for plain `zip_squash` means the long iterator is drained exactly, with no
+        // elements left over.
+        let mut long = 0..97;
+        let mut short = 0..13;
+        let count = zip_squash(&mut long, &mut short).count();
+        assert_eq!(count, 13);
+        assert_eq!(long.next(), None);
+        assert_eq!(short.next(), None);
+    }
+
+    #[test]
+    fn equal_lengths_is_a_plain_zip() {
+        let out: Vec<_> = zip_squash(0..5, 0..5).collect();
+        assert_eq!(out, (0..5).zip(0..5).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn either_side_empty_yields_nothing() {
+        assert_eq!(zip_squash(0..5, 0..0).count(), 0);
+        assert_eq!(zip_squash(0..0, 0..5).count(), 0);
+    }
+
+    #[test]
+    fn nth_matches_stepping_with_next() {
+        let expected: Vec<_> = zip_squash(0..97, 0..13).collect();
+        for skip in 0..expected.len() {
+            let mut it = zip_squash(0..97, 0..13);
+            assert_eq!(it.nth(skip), Some(expected[skip]));
+            // The iterator should also agree with `next()` on what comes after the skip.
+            assert_eq!(it.next(), expected.get(skip + 1).copied());
+        }
+    }
+
+    #[test]
+    fn nth_past_the_end_exhausts_the_iterator() {
+        let mut it = zip_squash(0..97, 0..13);
+        assert_eq!(it.nth(12), Some((96, 12)));
+        assert_eq!(it.nth(1), None);
+        assert_eq!(it.next(), None);
+    }
+
+    #[test]
+    fn rev_yields_the_same_pairs_in_reverse() {
+        let forward: Vec<_> = zip_squash(0..97, 0..13).collect();
+        let mut backward: Vec<_> = zip_squash(0..97, 0..13).rev().collect();
+        backward.reverse();
+        assert_eq!(forward, backward);
+    }
+
+    #[test]
+    fn front_and_back_meet_in_the_middle_with_no_overlap_or_gap() {
+        let expected: Vec<_> = zip_squash(0..97, 0..13).collect();
+        let mut it = zip_squash(0..97, 0..13);
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+        loop {
+            match (it.next(), it.next_back()) {
+                (Some(f), Some(b)) => {
+                    front.push(f);
+                    back.push(b);
+                }
+                (Some(f), None) => {
+                    front.push(f);
+                    break;
+                }
+                (None, Some(b)) => {
+                    back.push(b);
+                    break;
+                }
+                (None, None) => break,
+            }
+        }
+        back.reverse();
+        front.extend(back);
+        assert_eq!(front, expected);
+    }
+}
@@ -0,0 +1,308 @@
+use super::size_hint;
+
+/// An iterator which iterates two other iterators simultaneously, reducing each skipped
+/// run of the longer iterator through a closure instead of discarding it.
+///
+/// Each side gets its own closure (`fa` for `a`, `fb` for `b`) because the two iterators
+/// aren't required to yield the same item type, and only the closure for whichever side
+/// turns out to be longer is ever called. Each closure receives the whole run of skipped
+/// elements as a slice, rather than folding them pairwise, so it can compute things a
+/// pairwise reduction can't, such as an average.
+///
+/// See [`.zip_squash_by()`](crate::Itertools::zip_squash_by) for more information.
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ZipSquashBy<I, J, FA, FB>
+where
+    I: ExactSizeIterator,
+    J: ExactSizeIterator,
+    FA: FnMut(&[I::Item]) -> I::Item,
+    FB: FnMut(&[J::Item]) -> J::Item,
+{
+    a: I,
+    b: J,
+    fa: FA,
+    fb: FB,
+    // Whether `a` is the iterator being squashed down to `len_short` elements.
+    a_is_long: bool,
+    // Length of the shorter of the two iterators at construction time, i.e. the number of
+    // pairs this iterator will yield.
+    len_short: usize,
+    // `len_long / len_short`: the minimum number of long-side elements folded per pair.
+    base: usize,
+    // `len_long % len_short`: how often an extra long-side element must be folded in to make
+    // up the difference.
+    rem: usize,
+    // Bresenham-style error accumulator, kept in `[0, len_short)`.
+    error: usize,
+    // Number of pairs not yet yielded.
+    remaining: usize,
+    // Scratch buffer the current window of long-side `a` elements is collected into before
+    // being passed to `fa`. Reused across pairs to avoid reallocating every step.
+    window_a: Vec<I::Item>,
+    // As `window_a`, but for `b`.
+    window_b: Vec<J::Item>,
+}
+
+impl<I, J, FA, FB> Clone for ZipSquashBy<I, J, FA, FB>
+where
+    I: ExactSizeIterator + Clone,
+    J: ExactSizeIterator + Clone,
+    I::Item: Clone,
+    J::Item: Clone,
+    FA: FnMut(&[I::Item]) -> I::Item + Clone,
+    FB: FnMut(&[J::Item]) -> J::Item + Clone,
+{
+    fn clone(&self) -> Self {
+        ZipSquashBy {
+            a: self.a.clone(),
+            b: self.b.clone(),
+            fa: self.fa.clone(),
+            fb: self.fb.clone(),
+            a_is_long: self.a_is_long,
+            len_short: self.len_short,
+            base: self.base,
+            rem: self.rem,
+            error: self.error,
+            remaining: self.remaining,
+            window_a: self.window_a.clone(),
+            window_b: self.window_b.clone(),
+        }
+    }
+}
+
+impl<I, J, FA, FB> std::fmt::Debug for ZipSquashBy<I, J, FA, FB>
+where
+    I: ExactSizeIterator + std::fmt::Debug,
+    J: ExactSizeIterator + std::fmt::Debug,
+    I::Item: Clone + std::fmt::Debug,
+    J::Item: Clone + std::fmt::Debug,
+    FA: FnMut(&[I::Item]) -> I::Item,
+    FB: FnMut(&[J::Item]) -> J::Item,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ZipSquashBy")
+            .field("a", &self.a)
+            .field("b", &self.b)
+            .field("a_is_long", &self.a_is_long)
+            .field("len_short", &self.len_short)
+            .field("base", &self.base)
+            .field("rem", &self.rem)
+            .field("error", &self.error)
+            .field("remaining", &self.remaining)
+            .field("window_a", &self.window_a)
+            .field("window_b", &self.window_b)
+            .finish()
+    }
+}
+
+/// Zips two iterators, reducing the run of elements of the longer iterator skipped for each
+/// output through a closure rather than discarding all but one of them.
+///
+/// `fa` folds a skipped run of `a`'s elements when `a` turns out to be the longer iterator,
+/// and `fb` does the same for `b`; whichever side turns out to be shorter has its closure
+/// never called. Each closure receives the whole skipped run as a slice (never empty), which
+/// is enough to compute a sum, a max, or an average, unlike a pairwise reduction which can't
+/// tell how many elements it has folded.
+///
+/// [`IntoIterator`] enabled version of
+/// [`Itertools::zip_squash_by`](crate::Itertools::zip_squash_by).
+///
+/// ```
+/// use itertools::zip_squash_by;
+///
+/// let long = 0..10;
+/// let short = 0..3;
+/// let sum = |window: &[i32]| window.iter().sum();
+/// let squashed: Vec<_> = zip_squash_by(long, short, sum, sum).collect();
+/// assert_eq!(squashed, vec![(3, 0), (18, 1), (24, 2)]);
+/// ```
+pub fn zip_squash_by<I, J, FA, FB>(
+    i: I,
+    j: J,
+    fa: FA,
+    fb: FB,
+) -> ZipSquashBy<I::IntoIter, J::IntoIter, FA, FB>
+where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+    J::IntoIter: ExactSizeIterator,
+    FA: FnMut(&[<I::IntoIter as Iterator>::Item]) -> <I::IntoIter as Iterator>::Item,
+    FB: FnMut(&[<J::IntoIter as Iterator>::Item]) -> <J::IntoIter as Iterator>::Item,
+{
+    let (a, b) = (i.into_iter(), j.into_iter());
+    let (len_a, len_b) = (a.len(), b.len());
+    let len_long = len_a.max(len_b);
+    let len_short = len_a.min(len_b);
+    let base = len_long.checked_div(len_short).unwrap_or(0);
+    let rem = len_long.checked_rem(len_short).unwrap_or(0);
+    ZipSquashBy {
+        a,
+        b,
+        fa,
+        fb,
+        a_is_long: len_a >= len_b,
+        len_short,
+        base,
+        rem,
+        // Start halfway through the first window so sampled windows are centered rather than
+        // biased towards the start of the long iterator.
+        error: len_short / 2,
+        remaining: len_short,
+        window_a: Vec::new(),
+        window_b: Vec::new(),
+    }
+}
+
+impl<I, J, FA, FB> Iterator for ZipSquashBy<I, J, FA, FB>
+where
+    I: ExactSizeIterator,
+    J: ExactSizeIterator,
+    FA: FnMut(&[I::Item]) -> I::Item,
+    FB: FnMut(&[J::Item]) -> J::Item,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        // How many long-side elements make up this pair's window, exact for any length since
+        // it's computed with integer arithmetic only.
+        let mut advance = self.base;
+        self.error += self.rem;
+        if self.error >= self.len_short {
+            self.error -= self.len_short;
+            advance += 1;
+        }
+
+        if self.a_is_long {
+            self.window_a.clear();
+            for _ in 0..advance {
+                self.window_a.push(self.a.next()?);
+            }
+            let a = (self.fa)(&self.window_a);
+            let b = self.b.next()?;
+            Some((a, b))
+        } else {
+            let a = self.a.next()?;
+            self.window_b.clear();
+            for _ in 0..advance {
+                self.window_b.push(self.b.next()?);
+            }
+            let b = (self.fb)(&self.window_b);
+            Some((a, b))
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        size_hint::min(self.a.size_hint(), self.b.size_hint())
+    }
+}
+
+impl<I, J, FA, FB> ExactSizeIterator for ZipSquashBy<I, J, FA, FB>
+where
+    I: ExactSizeIterator,
+    J: ExactSizeIterator,
+    FA: FnMut(&[I::Item]) -> I::Item,
+    FB: FnMut(&[J::Item]) -> J::Item,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zip_squash_by;
+
+    fn sum(window: &[i32]) -> i32 {
+        window.iter().sum()
+    }
+
+    fn max(window: &[i32]) -> i32 {
+        *window.iter().max().unwrap()
+    }
+
+    fn average(window: &[i32]) -> i32 {
+        window.iter().sum::<i32>() / window.len() as i32
+    }
+
+    #[test]
+    fn length_is_the_shorter_iterator() {
+        let out: Vec<_> = zip_squash_by(0..97, 0..13, sum, sum).collect();
+        assert_eq!(out.len(), 13);
+    }
+
+    #[test]
+    fn long_side_is_fully_consumed() {
+        let mut long = 0..97;
+        let mut short = 0..13;
+        let count = zip_squash_by(&mut long, &mut short, sum, sum).count();
+        assert_eq!(count, 13);
+        assert_eq!(long.next(), None);
+        assert_eq!(short.next(), None);
+    }
+
+    #[test]
+    fn sum_folds_every_skipped_element_into_the_window() {
+        let out: Vec<_> = zip_squash_by(0..10, 0..3, sum, sum).collect();
+        let total: i32 = out.iter().map(|(a, _)| *a).sum();
+        assert_eq!(total, (0..10).sum());
+    }
+
+    #[test]
+    fn max_picks_the_largest_element_of_each_skipped_window() {
+        let out: Vec<_> = zip_squash_by(0..10, 0..3, max, max).collect();
+        let a_seq: Vec<_> = out.iter().map(|(a, _)| *a).collect();
+        assert_eq!(a_seq, vec![2, 6, 9]);
+    }
+
+    #[test]
+    fn average_divides_by_the_true_window_length() {
+        // A pairwise reduction can't implement this: it has no way to know how many
+        // elements it has folded, so it can't divide by the right count.
+        let out: Vec<_> = zip_squash_by(0..9, 0..3, average, average).collect();
+        let a_seq: Vec<_> = out.iter().map(|(a, _)| *a).collect();
+        assert_eq!(a_seq, vec![1, 4, 7]);
+    }
+
+    #[test]
+    fn heterogeneous_item_types_are_supported() {
+        let values = ["a", "b", "c", "d", "e", "f"];
+        let timestamps = 0..2;
+        let squashed: Vec<_> = zip_squash_by(
+            values,
+            timestamps,
+            |window: &[&str]| window[window.len() - 1],
+            |window: &[i32]| *window.last().unwrap(),
+        )
+        .collect();
+        assert_eq!(squashed, vec![("c", 0), ("f", 1)]);
+    }
+
+    #[test]
+    fn equal_lengths_is_a_plain_zip() {
+        let out: Vec<_> = zip_squash_by(0..5, 0..5, sum, sum).collect();
+        assert_eq!(out, (0..5).zip(0..5).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn either_side_empty_yields_nothing() {
+        assert_eq!(zip_squash_by(0..5, 0..0, sum, sum).count(), 0);
+        assert_eq!(zip_squash_by(0..0, 0..5, sum, sum).count(), 0);
+    }
+
+    #[test]
+    fn non_clone_items_are_supported() {
+        // `next()` only ever moves items into `window_a`/`window_b`, so folding a run of
+        // non-`Clone` items should work even though `ZipSquashBy` itself isn't `Clone` here.
+        #[derive(Debug, PartialEq)]
+        struct NotClone(i32);
+
+        let values = (0..9).map(NotClone);
+        let keep_last = |window: &[NotClone]| NotClone(window.last().unwrap().0);
+        let out: Vec<_> = zip_squash_by(values, 0..3, keep_last, sum).collect();
+        let a_seq: Vec<_> = out.into_iter().map(|(a, _)| a).collect();
+        assert_eq!(a_seq, vec![NotClone(2), NotClone(5), NotClone(8)]);
+    }
+}
@@ -0,0 +1,205 @@
+/// An iterator which iterates two other iterators simultaneously, holding and repeating
+/// elements of the shorter iterator so every element of the longer one is paired with
+/// something.
+///
+/// See [`.zip_stretch()`](crate::Itertools::zip_stretch) for more information.
+#[derive(Clone, Debug)]
+#[must_use = "iterator adaptors are lazy and do nothing unless consumed"]
+pub struct ZipStretch<I, J>
+where
+    I: ExactSizeIterator,
+    J: ExactSizeIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+{
+    a: I,
+    b: J,
+    // Whether `a` is the iterator every element of which is kept, i.e. the longer one.
+    a_is_long: bool,
+    // Length of the shorter of the two iterators at construction time, i.e. the number of
+    // distinct held values this iterator will produce.
+    len_short: usize,
+    // `len_long / len_short`: the minimum number of long-side pairs a held short-side element
+    // covers.
+    base: usize,
+    // `len_long % len_short`: how often a held element must cover one extra pair to make up
+    // the difference.
+    rem: usize,
+    // Bresenham-style error accumulator, kept in `[0, len_short)`.
+    error: usize,
+    // Long-side pairs remaining to be yielded for the currently held short-side element,
+    // before a fresh one must be fetched.
+    group_remaining: usize,
+    // Number of pairs not yet yielded.
+    remaining: usize,
+    // Most recently fetched element of `a`, held and cloned while `a` is the shorter
+    // iterator. `None` while `a` is the longer iterator, or before the first group starts.
+    held_a: Option<I::Item>,
+    // As `held_a`, but for `b` while `b` is the shorter iterator.
+    held_b: Option<J::Item>,
+}
+
+/// Zips two iterators, repeating elements of the shorter iterator so that every element of
+/// the longer iterator is paired with one.
+///
+/// [`IntoIterator`] enabled version of [`Itertools::zip_stretch`](crate::Itertools::zip_stretch).
+///
+/// ```
+/// use itertools::zip_stretch;
+///
+/// let long = 0..10;
+/// let short = 0..3;
+/// let stretched: Vec<_> = zip_stretch(long, short).collect();
+/// assert_eq!(
+///     stretched,
+///     vec![(0, 0), (1, 0), (2, 0), (3, 1), (4, 1), (5, 1), (6, 1), (7, 2), (8, 2), (9, 2)]
+/// );
+/// ```
+pub fn zip_stretch<I, J>(i: I, j: J) -> ZipStretch<I::IntoIter, J::IntoIter>
+where
+    I: IntoIterator,
+    J: IntoIterator,
+    I::IntoIter: ExactSizeIterator,
+    J::IntoIter: ExactSizeIterator,
+    <I::IntoIter as Iterator>::Item: Clone,
+    <J::IntoIter as Iterator>::Item: Clone,
+{
+    let (a, b) = (i.into_iter(), j.into_iter());
+    let (len_a, len_b) = (a.len(), b.len());
+    let len_long = len_a.max(len_b);
+    let len_short = len_a.min(len_b);
+    let base = len_long.checked_div(len_short).unwrap_or(0);
+    let rem = len_long.checked_rem(len_short).unwrap_or(0);
+    ZipStretch {
+        a,
+        b,
+        a_is_long: len_a >= len_b,
+        len_short,
+        base,
+        rem,
+        // Start halfway through the first group so the point where a held value changes is
+        // centered rather than biased towards the start, mirroring how `ZipSquash` centers
+        // its own error accumulator.
+        error: len_short / 2,
+        group_remaining: 0,
+        // If the shorter iterator is empty there's nothing to hold, so no pair can ever be
+        // produced, regardless of how long the longer iterator is.
+        remaining: if len_short == 0 { 0 } else { len_long },
+        held_a: None,
+        held_b: None,
+    }
+}
+
+impl<I, J> Iterator for ZipStretch<I, J>
+where
+    I: ExactSizeIterator,
+    J: ExactSizeIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+{
+    type Item = (I::Item, J::Item);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        if self.group_remaining == 0 {
+            // The currently held value has covered as many pairs as its group allows (or this
+            // is the very first pair); fetch a fresh short-side element and compute how many
+            // long-side pairs it covers, exact for any length since it's computed with
+            // integer arithmetic only.
+            let mut advance = self.base;
+            self.error += self.rem;
+            if self.error >= self.len_short {
+                self.error -= self.len_short;
+                advance += 1;
+            }
+            self.group_remaining = advance;
+            if self.a_is_long {
+                self.held_b = self.b.next();
+            } else {
+                self.held_a = self.a.next();
+            }
+        }
+        self.group_remaining -= 1;
+
+        if self.a_is_long {
+            match (self.a.next(), self.held_b.clone()) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            }
+        } else {
+            match (self.held_a.clone(), self.b.next()) {
+                (Some(a), Some(b)) => Some((a, b)),
+                _ => None,
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<I, J> ExactSizeIterator for ZipStretch<I, J>
+where
+    I: ExactSizeIterator,
+    J: ExactSizeIterator,
+    I::Item: Clone,
+    J::Item: Clone,
+{
+}
+
+#[cfg(test)]
+mod tests {
+    use super::zip_stretch;
+
+    #[test]
+    fn length_is_the_longer_iterator() {
+        let out: Vec<_> = zip_stretch(0..97, 0..13).collect();
+        assert_eq!(out.len(), 97);
+    }
+
+    #[test]
+    fn every_long_value_is_kept_in_order() {
+        let out: Vec<_> = zip_stretch(0..97, 0..13).collect();
+        let a_seq: Vec<_> = out.iter().map(|(a, _)| *a).collect();
+        assert_eq!(a_seq, (0..97).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn every_short_value_is_held_in_order_and_covers_the_whole_range() {
+        let out: Vec<_> = zip_stretch(0..97, 0..13).collect();
+        let b_seq: Vec<_> = out.iter().map(|(_, b)| *b).collect();
+        assert!(b_seq.windows(2).all(|w| w[0] <= w[1]));
+        assert_eq!(b_seq.first().copied(), Some(0));
+        assert_eq!(b_seq.last().copied(), Some(12));
+        let distinct: std::collections::BTreeSet<_> = b_seq.into_iter().collect();
+        assert_eq!(distinct.len(), 13);
+    }
+
+    #[test]
+    fn equal_lengths_is_a_plain_zip() {
+        let out: Vec<_> = zip_stretch(0..5, 0..5).collect();
+        assert_eq!(out, (0..5).zip(0..5).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn either_side_empty_yields_nothing() {
+        assert_eq!(zip_stretch(0..5, 0..0).count(), 0);
+        assert_eq!(zip_stretch(0..0, 0..5).count(), 0);
+    }
+
+    #[test]
+    fn either_side_empty_reports_zero_len() {
+        let empty_short = zip_stretch(0..5, 0..0);
+        assert_eq!(empty_short.len(), 0);
+        assert_eq!(empty_short.size_hint(), (0, Some(0)));
+
+        let empty_long = zip_stretch(0..0, 0..5);
+        assert_eq!(empty_long.len(), 0);
+        assert_eq!(empty_long.size_hint(), (0, Some(0)));
+    }
+}